@@ -0,0 +1,265 @@
+//! Pluggable persistence for [`TaskCollection`], gated behind the `serialize` feature.
+//!
+//! [`Backend`] is deliberately minimal so downstream crates can plug in their own storage
+//! (sqlite, nostr, ...) without needing changes here; this crate ships [`JsonBackend`] for
+//! plain round-tripping and [`ICalBackend`] for interop with calendar/todo apps that speak
+//! iCalendar (RFC 5545) `VTODO`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::taskwarrior::{format_taskwarrior_date, parse_taskwarrior_date};
+use crate::{Collection, Task, TaskCollection, TaskStatus};
+
+/// Errors that can occur while saving or loading a [`TaskCollection`].
+#[derive(Debug)]
+pub enum BackendError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The file's contents couldn't be parsed by this backend.
+    Format(String),
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::Io(e) => write!(f, "io error: {e}"),
+            BackendError::Json(e) => write!(f, "json error: {e}"),
+            BackendError::Format(msg) => write!(f, "format error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl From<io::Error> for BackendError {
+    fn from(e: io::Error) -> Self {
+        BackendError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for BackendError {
+    fn from(e: serde_json::Error) -> Self {
+        BackendError::Json(e)
+    }
+}
+
+/// A storage backend for a [`TaskCollection`]. Implement this to add a new persistence
+/// format without modifying this crate.
+pub trait Backend {
+    fn save(collection: &TaskCollection, path: &Path) -> Result<(), BackendError>;
+    fn load(path: &Path) -> Result<TaskCollection, BackendError>;
+}
+
+/// Plain JSON persistence, using the same `Serialize`/`Deserialize` impls as the rest of the
+/// `serialize` feature.
+pub struct JsonBackend;
+
+impl Backend for JsonBackend {
+    fn save(collection: &TaskCollection, path: &Path) -> Result<(), BackendError> {
+        let json = serde_json::to_string_pretty(collection)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    fn load(path: &Path) -> Result<TaskCollection, BackendError> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+/// iCalendar persistence. Each [`Task`] is stored as a `VTODO` component carrying its uuid,
+/// title, description, completion time and due date, so the file can be opened directly by
+/// any standard calendar/todo application. Priority, project, tags, entry time, dependencies,
+/// parent and recurrence are not stored; on load they take their [`Task::new`] defaults.
+pub struct ICalBackend;
+
+impl Backend for ICalBackend {
+    fn save(collection: &TaskCollection, path: &Path) -> Result<(), BackendError> {
+        let mut out = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//seybio-task-manager//EN\r\n");
+        for task in &collection.tasks {
+            out.push_str(&task_to_vtodo(task));
+        }
+        out.push_str("END:VCALENDAR\r\n");
+        fs::write(path, out)?;
+        Ok(())
+    }
+
+    fn load(path: &Path) -> Result<TaskCollection, BackendError> {
+        let contents = fs::read_to_string(path)?;
+        let mut collection = TaskCollection::new();
+        for block in contents.split("BEGIN:VTODO").skip(1) {
+            let block = block.split("END:VTODO").next().unwrap_or("");
+            collection.add_task(vtodo_to_task(block)?);
+        }
+        Ok(collection)
+    }
+}
+
+fn task_to_vtodo(task: &Task) -> String {
+    let mut vtodo = String::from("BEGIN:VTODO\r\n");
+    vtodo.push_str(&format!("UID:{}\r\n", task.uuid));
+    vtodo.push_str(&format!("SUMMARY:{}\r\n", escape_ical_text(&task.title)));
+    if !task.description.is_empty() {
+        vtodo.push_str(&format!(
+            "DESCRIPTION:{}\r\n",
+            escape_ical_text(&task.description)
+        ));
+    }
+    match task.completed_at {
+        Some(completed_at) => {
+            vtodo.push_str("STATUS:COMPLETED\r\n");
+            vtodo.push_str(&format!(
+                "COMPLETED:{}\r\n",
+                format_taskwarrior_date(completed_at)
+            ));
+        }
+        None => vtodo.push_str("STATUS:NEEDS-ACTION\r\n"),
+    }
+    if let Some(due) = task.due {
+        vtodo.push_str(&format!("DUE:{}\r\n", format_taskwarrior_date(due)));
+    }
+    vtodo.push_str("END:VTODO\r\n");
+    vtodo
+}
+
+fn vtodo_to_task(block: &str) -> Result<Task, BackendError> {
+    let mut task = Task::new("");
+    let mut has_summary = false;
+
+    for line in block.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key {
+            "UID" => {
+                task.uuid = Uuid::parse_str(value)
+                    .map_err(|e| BackendError::Format(format!("bad UID: {e}")))?;
+            }
+            "SUMMARY" => {
+                task.title = unescape_ical_text(value);
+                has_summary = true;
+            }
+            "DESCRIPTION" => task.description = unescape_ical_text(value),
+            "COMPLETED" => {
+                task.completed_at = Some(
+                    parse_taskwarrior_date(value)
+                        .map_err(|e| BackendError::Format(format!("bad COMPLETED: {e}")))?,
+                );
+                task.status = TaskStatus::Completed;
+            }
+            "DUE" => {
+                task.due = Some(
+                    parse_taskwarrior_date(value)
+                        .map_err(|e| BackendError::Format(format!("bad DUE: {e}")))?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if !has_summary {
+        return Err(BackendError::Format("VTODO missing SUMMARY".to_string()));
+    }
+    Ok(task)
+}
+
+fn escape_ical_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ',' => out.push_str("\\,"),
+            ';' => out.push_str("\\;"),
+            '\n' => out.push_str("\\n"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Inverse of [`escape_ical_text`]. Written as a single left-to-right pass (rather than
+/// chained `.replace()` calls) because the escapes aren't independent: e.g. an escaped
+/// backslash (`\\\\`) immediately followed by a literal `n` looks, to a naive substring
+/// search, like an escaped newline (`\\n`) once the first replace has run.
+fn unescape_ical_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some(';') => out.push(';'),
+            Some(',') => out.push(','),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+impl TaskCollection {
+    /// Saves this collection using the given [`Backend`].
+    pub fn save_to<B: Backend>(&self, path: &Path) -> Result<(), BackendError> {
+        B::save(self, path)
+    }
+
+    /// Loads a collection using the given [`Backend`].
+    pub fn load_from<B: Backend>(path: &Path) -> Result<TaskCollection, BackendError> {
+        B::load(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ical_text_escaping_round_trips_a_trailing_backslash_before_n() {
+        let text = "C:\\new_project\\notes.txt";
+        assert_eq!(unescape_ical_text(&escape_ical_text(text)), text);
+    }
+
+    #[test]
+    fn json_backend_round_trips() {
+        let dir = std::env::temp_dir().join(format!("task-manager-test-{}", Uuid::new_v4()));
+        let mut collection = TaskCollection::new();
+        collection.add_task(Task::new("write the backend"));
+
+        collection.save_to::<JsonBackend>(&dir).unwrap();
+        let loaded = TaskCollection::load_from::<JsonBackend>(&dir).unwrap();
+        assert_eq!(loaded.tasks.len(), 1);
+        assert_eq!(loaded.tasks[0].title, "write the backend");
+
+        fs::remove_file(&dir).unwrap();
+    }
+
+    #[test]
+    fn ical_backend_round_trips() {
+        let dir = std::env::temp_dir().join(format!("task-manager-test-{}", Uuid::new_v4()));
+        let mut collection = TaskCollection::new();
+        let mut task = Task::new("buy milk, eggs");
+        task.description = "don't forget the farmer's market".to_string();
+        collection.add_task(task.clone());
+
+        collection.save_to::<ICalBackend>(&dir).unwrap();
+        let loaded = TaskCollection::load_from::<ICalBackend>(&dir).unwrap();
+        assert_eq!(loaded.tasks.len(), 1);
+        assert_eq!(loaded.tasks[0].uuid, task.uuid);
+        assert_eq!(loaded.tasks[0].title, task.title);
+        assert_eq!(loaded.tasks[0].description, task.description);
+
+        fs::remove_file(&dir).unwrap();
+    }
+}
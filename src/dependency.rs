@@ -0,0 +1,162 @@
+//! Dependency graph resolution over a [`TaskCollection`]'s `depends_on` edges.
+
+use std::fmt;
+
+use uuid::Uuid;
+
+use crate::{Task, TaskCollection};
+
+/// Errors produced while resolving the dependency graph.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DependencyError {
+    /// The dependency chain forms a cycle; the UUIDs are the offending chain, in order.
+    Cycle(Vec<Uuid>),
+    /// A task depends on a UUID that isn't present in the collection.
+    NotFound(Uuid),
+}
+
+impl fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyError::Cycle(chain) => write!(f, "dependency cycle: {chain:?}"),
+            DependencyError::NotFound(uuid) => write!(f, "dependency not found: {uuid}"),
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Color {
+    Unvisited,
+    Visiting,
+    Visited,
+}
+
+impl TaskCollection {
+    /// Tasks that depend on `task`, i.e. that are blocked by it.
+    pub fn blocking<'a>(&'a self, task: &Task) -> Vec<&'a Task> {
+        self.tasks
+            .iter()
+            .filter(|t| t.depends_on.contains(&task.uuid))
+            .collect()
+    }
+
+    /// Tasks that `task` depends on, i.e. that block it.
+    pub fn blocked<'a>(&'a self, task: &'a Task) -> Vec<&'a Task> {
+        task.depends_on
+            .iter()
+            .filter_map(|id| self.tasks.iter().find(|t| t.uuid == *id))
+            .collect()
+    }
+
+    /// Returns tasks ordered so that every dependency precedes its dependents, computed via a
+    /// depth-first topological sort.
+    pub fn resolution_order(&self) -> Result<Vec<&Task>, DependencyError> {
+        let mut colors = vec![Color::Unvisited; self.tasks.len()];
+        let mut order = Vec::with_capacity(self.tasks.len());
+        let mut stack: Vec<Uuid> = Vec::new();
+
+        for start in 0..self.tasks.len() {
+            if colors[start] == Color::Unvisited {
+                self.visit(start, &mut colors, &mut stack, &mut order)?;
+            }
+        }
+
+        Ok(order)
+    }
+
+    fn visit<'a>(
+        &'a self,
+        index: usize,
+        colors: &mut Vec<Color>,
+        stack: &mut Vec<Uuid>,
+        order: &mut Vec<&'a Task>,
+    ) -> Result<(), DependencyError> {
+        let task = &self.tasks[index];
+        colors[index] = Color::Visiting;
+        stack.push(task.uuid);
+
+        for dep_id in &task.depends_on {
+            let dep_index = self
+                .tasks
+                .iter()
+                .position(|t| t.uuid == *dep_id)
+                .ok_or(DependencyError::NotFound(*dep_id))?;
+
+            match colors[dep_index] {
+                Color::Unvisited => self.visit(dep_index, colors, stack, order)?,
+                Color::Visiting => {
+                    let cycle_start = stack.iter().position(|id| *id == *dep_id).unwrap_or(0);
+                    let mut chain = stack[cycle_start..].to_vec();
+                    chain.push(*dep_id);
+                    return Err(DependencyError::Cycle(chain));
+                }
+                Color::Visited => {}
+            }
+        }
+
+        stack.pop();
+        colors[index] = Color::Visited;
+        order.push(task);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collection;
+
+    #[test]
+    fn resolves_a_simple_chain() {
+        let mut collection = TaskCollection::new();
+        let first = Task::new("first");
+        let mut second = Task::new("second");
+        second.depends_on.push(first.uuid);
+        collection.add_task(first.clone());
+        collection.add_task(second.clone());
+
+        let order = collection.resolution_order().unwrap();
+        assert_eq!(order[0].uuid, first.uuid);
+        assert_eq!(order[1].uuid, second.uuid);
+    }
+
+    #[test]
+    fn detects_a_cycle() {
+        let mut collection = TaskCollection::new();
+        let mut first = Task::new("first");
+        let mut second = Task::new("second");
+        first.depends_on.push(second.uuid);
+        second.depends_on.push(first.uuid);
+        collection.add_task(first);
+        collection.add_task(second);
+
+        let err = collection.resolution_order().unwrap_err();
+        assert!(matches!(err, DependencyError::Cycle(_)));
+    }
+
+    #[test]
+    fn reports_missing_dependency() {
+        let mut collection = TaskCollection::new();
+        let mut task = Task::new("orphaned dependency");
+        task.depends_on.push(Uuid::new_v4());
+        collection.add_task(task);
+
+        let err = collection.resolution_order().unwrap_err();
+        assert!(matches!(err, DependencyError::NotFound(_)));
+    }
+
+    #[test]
+    fn blocking_and_blocked_report_the_right_tasks() {
+        let mut collection = TaskCollection::new();
+        let first = Task::new("first");
+        let mut second = Task::new("second");
+        second.depends_on.push(first.uuid);
+        collection.add_task(first.clone());
+        collection.add_task(second.clone());
+
+        assert_eq!(collection.blocking(&first)[0].uuid, second.uuid);
+        assert_eq!(collection.blocked(&second)[0].uuid, first.uuid);
+    }
+}
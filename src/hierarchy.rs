@@ -0,0 +1,275 @@
+//! Parent/child task hierarchies with incrementally-maintained rollup summaries.
+//!
+//! Children aren't stored on the parent; they're derived by scanning the collection for
+//! `parent == Some(root)`. The summaries in [`TaskCollection::subtree_cache`] are what make
+//! repeated queries cheap: once a subtree has been summarized, flipping a descendant's
+//! completion via [`TaskCollection::set_task_completed`] only walks upward from that node to
+//! the root, adjusting counters along the way (`O(depth)`), rather than re-walking the whole
+//! subtree on every query (`O(n)`).
+//!
+//! That incremental maintenance only covers completion toggles. Anything that changes
+//! subtree *membership* — adding or removing a task, or reassigning `parent` via
+//! [`TaskCollection::set_parent`] or a raw field write through [`TaskCollection::edit_task`] —
+//! invalidates the whole cache instead of trying to patch it up, since such an edit can affect
+//! an arbitrary, unbounded set of cached roots.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::{Collection, TaskCollection};
+
+/// A cached rollup for a subtree rooted at some task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubtreeSummary {
+    pub total_count: usize,
+    pub unfinished_count: usize,
+    pub fully_complete: bool,
+    /// Descendants that have changed since this summary was last read.
+    pub dirty: HashSet<Uuid>,
+}
+
+impl TaskCollection {
+    /// Returns the (possibly cached) rollup summary for the subtree rooted at `root`.
+    pub fn subtree_summary(&mut self, root: Uuid) -> SubtreeSummary {
+        if !self.subtree_cache.contains_key(&root) {
+            let summary = self.compute_subtree_summary(root);
+            self.subtree_cache.insert(root, summary);
+        }
+        let summary = self.subtree_cache.get_mut(&root).unwrap();
+        let result = summary.clone();
+        summary.dirty.clear();
+        result
+    }
+
+    /// Fraction of the subtree rooted at `root` that is complete, for progress UIs like "7/10".
+    pub fn completion_progress(&mut self, root: Uuid) -> f32 {
+        let summary = self.subtree_summary(root);
+        if summary.total_count == 0 {
+            return 1.0;
+        }
+        (summary.total_count - summary.unfinished_count) as f32 / summary.total_count as f32
+    }
+
+    /// Marks the task `id` as complete or incomplete, updating any cached ancestor summaries
+    /// incrementally instead of invalidating them. If completing `id` spawns a new recurring
+    /// instance (see [`crate::Task::complete`]), it's added to the collection automatically;
+    /// like any added task, that clears the whole cache.
+    pub fn set_task_completed(&mut self, id: Uuid, completed: bool) {
+        let was_completed = match self.tasks.iter().find(|t| t.uuid == id) {
+            Some(task) => task.completed_at.is_some(),
+            None => return,
+        };
+        if was_completed == completed {
+            return;
+        }
+
+        let mut spawned = None;
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.uuid == id) {
+            if completed {
+                spawned = task.complete();
+            } else {
+                task.uncomplete();
+            }
+        }
+        // `complete()` may hand back a fresh recurring instance; without adding it here it
+        // would silently vanish for anyone going through this API instead of `Task::complete`.
+        if let Some(next) = spawned {
+            self.add_task(next);
+        }
+
+        let delta: i64 = if completed { -1 } else { 1 };
+        let mut visited = HashSet::new();
+        let mut cursor = Some(id);
+        while let Some(current) = cursor {
+            if !visited.insert(current) {
+                // A cycle in `parent` links; stop walking instead of looping forever.
+                break;
+            }
+            if let Some(summary) = self.subtree_cache.get_mut(&current) {
+                summary.unfinished_count = (summary.unfinished_count as i64 + delta).max(0) as usize;
+                summary.fully_complete = summary.unfinished_count == 0;
+                summary.dirty.insert(id);
+            }
+            cursor = self.tasks.iter().find(|t| t.uuid == current).and_then(|t| t.parent);
+        }
+    }
+
+    /// Reassigns the task `id`'s parent, invalidating the whole subtree cache since the move
+    /// can affect any number of previously-cached roots.
+    pub fn set_parent(&mut self, id: Uuid, parent: Option<Uuid>) {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.uuid == id) {
+            task.parent = parent;
+            self.subtree_cache.clear();
+        }
+    }
+
+    fn children_of(&self, id: Uuid) -> Vec<Uuid> {
+        self.tasks
+            .iter()
+            .filter(|t| t.parent == Some(id))
+            .map(|t| t.uuid)
+            .collect()
+    }
+
+    fn compute_subtree_summary(&self, root: Uuid) -> SubtreeSummary {
+        let mut total = 0;
+        let mut unfinished = 0;
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            // `parent` is a plain public field, so nothing stops a cycle (`a.parent = Some(b)`,
+            // `b.parent = Some(a)`) from being constructed; guard the walk so that can only
+            // ever visit each task once instead of looping forever.
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(task) = self.tasks.iter().find(|t| t.uuid == id) {
+                total += 1;
+                if task.completed_at.is_none() {
+                    unfinished += 1;
+                }
+            }
+            stack.extend(self.children_of(id));
+        }
+        SubtreeSummary {
+            total_count: total,
+            unfinished_count: unfinished,
+            fully_complete: unfinished == 0,
+            dirty: HashSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collection, Task};
+
+    fn child_of(parent: &Task, title: &str) -> Task {
+        let mut task = Task::new(title);
+        task.parent = Some(parent.uuid);
+        task
+    }
+
+    #[test]
+    fn summarizes_a_fresh_subtree() {
+        let mut collection = TaskCollection::new();
+        let root = Task::new("root");
+        let child1 = child_of(&root, "child 1");
+        let child2 = child_of(&root, "child 2");
+        collection.add_task(root.clone());
+        collection.add_task(child1);
+        collection.add_task(child2);
+
+        let summary = collection.subtree_summary(root.uuid);
+        assert_eq!(summary.total_count, 3);
+        assert_eq!(summary.unfinished_count, 3);
+        assert!(!summary.fully_complete);
+    }
+
+    #[test]
+    fn completing_a_child_updates_cached_ancestor_summary() {
+        let mut collection = TaskCollection::new();
+        let root = Task::new("root");
+        let child = child_of(&root, "child");
+        let child_uuid = child.uuid;
+        collection.add_task(root.clone());
+        collection.add_task(child);
+
+        collection.subtree_summary(root.uuid);
+        collection.set_task_completed(child_uuid, true);
+
+        let summary = collection.subtree_summary(root.uuid);
+        assert_eq!(summary.unfinished_count, 1);
+        assert!(!summary.fully_complete);
+    }
+
+    #[test]
+    fn a_parent_cycle_does_not_hang() {
+        let mut collection = TaskCollection::new();
+        let mut a = Task::new("a");
+        let mut b = Task::new("b");
+        a.parent = Some(b.uuid);
+        b.parent = Some(a.uuid);
+        let a_uuid = a.uuid;
+        collection.add_task(a);
+        collection.add_task(b);
+
+        let summary = collection.subtree_summary(a_uuid);
+        assert_eq!(summary.total_count, 2);
+
+        collection.set_task_completed(a_uuid, true);
+    }
+
+    #[test]
+    fn adding_a_child_after_caching_invalidates_the_summary() {
+        let mut collection = TaskCollection::new();
+        let root = Task::new("root");
+        collection.add_task(root.clone());
+
+        let summary = collection.subtree_summary(root.uuid);
+        assert_eq!(summary.total_count, 1);
+
+        collection.add_task(child_of(&root, "late child"));
+        let summary = collection.subtree_summary(root.uuid);
+        assert_eq!(summary.total_count, 2);
+    }
+
+    #[test]
+    fn set_parent_invalidates_the_summary() {
+        let mut collection = TaskCollection::new();
+        let root_a = Task::new("root a");
+        let root_b = Task::new("root b");
+        let child = child_of(&root_a, "child");
+        let child_uuid = child.uuid;
+        collection.add_task(root_a.clone());
+        collection.add_task(root_b.clone());
+        collection.add_task(child);
+
+        assert_eq!(collection.subtree_summary(root_a.uuid).total_count, 2);
+        assert_eq!(collection.subtree_summary(root_b.uuid).total_count, 1);
+
+        collection.set_parent(child_uuid, Some(root_b.uuid));
+
+        assert_eq!(collection.subtree_summary(root_a.uuid).total_count, 1);
+        assert_eq!(collection.subtree_summary(root_b.uuid).total_count, 2);
+    }
+
+    #[test]
+    fn completing_a_recurring_task_through_set_task_completed_adds_the_next_instance() {
+        use crate::Recurrence;
+        use std::time::SystemTime;
+
+        let mut collection = TaskCollection::new();
+        let mut task = Task::new("water the plants");
+        task.due = Some(SystemTime::now());
+        task.recurrence = Some(Recurrence::Daily);
+        let task_uuid = task.uuid;
+        collection.add_task(task);
+
+        collection.set_task_completed(task_uuid, true);
+
+        assert_eq!(collection.tasks.len(), 2);
+        assert!(collection
+            .tasks
+            .iter()
+            .any(|t| t.uuid != task_uuid && t.completed_at.is_none()));
+    }
+
+    #[test]
+    fn completion_progress_reports_a_fraction() {
+        let mut collection = TaskCollection::new();
+        let root = Task::new("root");
+        let child1 = child_of(&root, "child 1");
+        let child1_uuid = child1.uuid;
+        let child2 = child_of(&root, "child 2");
+        collection.add_task(root.clone());
+        collection.add_task(child1);
+        collection.add_task(child2);
+
+        collection.set_task_completed(child1_uuid, true);
+        let progress = collection.completion_progress(root.uuid);
+        assert!((progress - (1.0 / 3.0)).abs() < f32::EPSILON);
+    }
+}
@@ -1,31 +1,93 @@
 use std::time::SystemTime;
 
+use uuid::Uuid;
+
+#[cfg(feature = "serialize")]
+mod backend;
+mod dependency;
+mod hierarchy;
+mod project;
+mod recurrence;
+mod taskwarrior;
+mod urgency;
+#[cfg(feature = "serialize")]
+pub use backend::{Backend, BackendError, ICalBackend, JsonBackend};
+pub use dependency::DependencyError;
+pub use hierarchy::SubtreeSummary;
+pub use project::{ProjectView, TaskError};
+pub use recurrence::{DateParseError, Recurrence};
+pub use taskwarrior::{Priority, TaskStatus, TaskwarriorError};
+pub use urgency::UrgencyConfig;
+
+use std::collections::HashMap;
+
 /// Represents a task with a title, description, and completion status.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Clone)]
 pub struct Task {
+    pub uuid: Uuid,
     pub title: String,
     pub description: String,
+    /// When this task was created. Mirrors Taskwarrior's `entry` attribute.
+    pub entry: SystemTime,
+    pub status: TaskStatus,
+    pub priority: Option<Priority>,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub due: Option<SystemTime>,
     pub completed_at: Option<SystemTime>,
+    /// UUIDs of tasks that must complete before this one can start.
+    pub depends_on: Vec<Uuid>,
+    /// UUID of this task's parent, if it's a subtask. Children are derived by scanning the
+    /// collection rather than stored on the parent.
+    pub parent: Option<Uuid>,
+    /// If set, completing this task spawns a fresh instance with `due` advanced by this
+    /// interval instead of just marking it done.
+    pub recurrence: Option<Recurrence>,
 }
 
 impl Task {
     pub fn new(title: &str) -> Self {
         Self {
+            uuid: Uuid::new_v4(),
             title: title.to_string(),
             description: "".to_string(),
+            entry: SystemTime::now(),
+            status: TaskStatus::Pending,
+            priority: None,
+            project: None,
+            tags: vec![],
+            due: None,
             completed_at: None,
+            depends_on: vec![],
+            parent: None,
+            recurrence: None,
         }
     }
 
-    pub fn complete(&mut self) {
-        match self.completed_at {
-            None => self.completed_at = Some(SystemTime::now()),
-            x => self.completed_at = x,
+    /// Marks this task complete. If it's a no-op (already complete) returns `None`;
+    /// otherwise returns `Some(next)` with a fresh recurring instance when `recurrence` is
+    /// set, which the caller should add to the collection.
+    pub fn complete(&mut self) -> Option<Task> {
+        if self.completed_at.is_some() {
+            return None;
         }
+        self.completed_at = Some(SystemTime::now());
+        self.status = TaskStatus::Completed;
+
+        self.recurrence.map(|recurrence| {
+            let mut next = self.clone();
+            next.uuid = Uuid::new_v4();
+            next.entry = SystemTime::now();
+            next.completed_at = None;
+            next.status = TaskStatus::Pending;
+            next.due = self.due.map(|due| recurrence.advance(due));
+            next
+        })
     }
     pub fn uncomplete(&mut self) {
-        self.completed_at = None
+        self.completed_at = None;
+        self.status = TaskStatus::Pending;
     }
 }
 
@@ -88,20 +150,31 @@ pub trait Collection {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaskCollection {
     pub tasks: Vec<Task>,
+    /// Cached per-subtree rollups, keyed by subtree root. Populated lazily by
+    /// `subtree_summary` and kept up to date incrementally as completion state changes.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    subtree_cache: HashMap<Uuid, SubtreeSummary>,
 }
 
 impl Collection for TaskCollection {
     type Task = Task;
 
     fn new() -> Self {
-        Self { tasks: vec![] }
+        Self {
+            tasks: vec![],
+            subtree_cache: HashMap::new(),
+        }
     }
     fn add_task(&mut self, task: Self::Task) {
         self.tasks.push(task);
+        // A new task can change the membership of any cached subtree (it may be a child of an
+        // already-summarized root), so the cache can't be trusted to stay correct incrementally.
+        self.subtree_cache.clear();
     }
 
     fn remove_task(&mut self, task: Self::Task) {
         self.tasks.retain(|t| *t != task);
+        self.subtree_cache.clear();
     }
 }
 
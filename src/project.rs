@@ -0,0 +1,149 @@
+//! Project grouping over a [`TaskCollection`], modeled on rustask's project type: tasks are
+//! addressed by their stable position in the collection rather than by value, since titles
+//! (and now projects) can collide.
+
+use crate::{Task, TaskCollection};
+
+/// Errors produced by index-addressed operations on a [`TaskCollection`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TaskError {
+    OutOfBounds,
+}
+
+impl std::fmt::Display for TaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskError::OutOfBounds => write!(f, "task index out of bounds"),
+        }
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+/// A read-only, title-sorted view of the tasks belonging to one project.
+#[derive(Debug, PartialEq)]
+pub struct ProjectView<'a> {
+    pub name: String,
+    pub tasks: Vec<&'a Task>,
+}
+
+impl TaskCollection {
+    /// Returns the tasks belonging to `name`, sorted by title.
+    pub fn project(&self, name: &str) -> ProjectView<'_> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.project.as_deref() == Some(name))
+            .collect();
+        tasks.sort_by(|a, b| a.title.cmp(&b.title));
+        ProjectView {
+            name: name.to_string(),
+            tasks,
+        }
+    }
+
+    /// Assigns the task at `index` to `project`.
+    pub fn add_to_project(&mut self, index: usize, project: &str) -> Result<(), TaskError> {
+        let task = self.tasks.get_mut(index).ok_or(TaskError::OutOfBounds)?;
+        task.project = Some(project.to_string());
+        Ok(())
+    }
+
+    /// Renames every task currently in project `old` to project `new`.
+    pub fn rename_project(&mut self, old: &str, new: &str) {
+        for task in self.tasks.iter_mut() {
+            if task.project.as_deref() == Some(old) {
+                task.project = Some(new.to_string());
+            }
+        }
+    }
+
+    /// Mutates the task at `index` in place via `transform`, re-sorting the collection by
+    /// title afterwards so its tasks stay addressable in a stable, predictable order.
+    ///
+    /// `transform` can reassign `parent`/`completed_at` arbitrarily, so any cached subtree
+    /// rollups are invalidated unconditionally rather than trusted to still be correct.
+    pub fn edit_task<F: FnOnce(&mut Task)>(
+        &mut self,
+        index: usize,
+        transform: F,
+    ) -> Result<(), TaskError> {
+        let task = self.tasks.get_mut(index).ok_or(TaskError::OutOfBounds)?;
+        transform(task);
+        self.tasks.sort_by(|a, b| a.title.cmp(&b.title));
+        self.subtree_cache.clear();
+        Ok(())
+    }
+
+    /// Removes and returns the task at `index`, for when removing by value ([`crate::Collection::remove_task`])
+    /// is ambiguous because titles collide.
+    pub fn remove_task_by_index(&mut self, index: usize) -> Result<Task, TaskError> {
+        if index >= self.tasks.len() {
+            return Err(TaskError::OutOfBounds);
+        }
+        let removed = self.tasks.remove(index);
+        self.subtree_cache.clear();
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collection;
+
+    #[test]
+    fn project_view_is_sorted_by_title() {
+        let mut collection = TaskCollection::new();
+        let mut b = Task::new("b task");
+        b.project = Some("demo".to_string());
+        let mut a = Task::new("a task");
+        a.project = Some("demo".to_string());
+        collection.add_task(b);
+        collection.add_task(a);
+
+        let view = collection.project("demo");
+        assert_eq!(view.tasks.len(), 2);
+        assert_eq!(view.tasks[0].title, "a task");
+        assert_eq!(view.tasks[1].title, "b task");
+    }
+
+    #[test]
+    fn add_to_project_out_of_bounds() {
+        let mut collection = TaskCollection::new();
+        assert_eq!(
+            collection.add_to_project(0, "demo"),
+            Err(TaskError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn rename_project_updates_all_matching_tasks() {
+        let mut collection = TaskCollection::new();
+        let mut task = Task::new("task");
+        task.project = Some("old".to_string());
+        collection.add_task(task);
+
+        collection.rename_project("old", "new");
+        assert_eq!(collection.tasks[0].project.as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn edit_task_applies_transform_and_resorts() {
+        let mut collection = TaskCollection::new();
+        collection.add_task(Task::new("b task"));
+        collection.add_task(Task::new("a task"));
+
+        collection.edit_task(0, |t| t.title = "z task".to_string()).unwrap();
+        assert_eq!(collection.tasks.last().unwrap().title, "z task");
+    }
+
+    #[test]
+    fn remove_task_by_index_out_of_bounds() {
+        let mut collection = TaskCollection::new();
+        assert_eq!(
+            collection.remove_task_by_index(0),
+            Err(TaskError::OutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Natural-language due dates and recurrence.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::taskwarrior::days_from_civil;
+use crate::{Task, TaskCollection};
+
+const SECS_PER_DAY: u64 = 24 * 3600;
+
+/// How a completed recurring task's next instance is scheduled.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Every(Duration),
+}
+
+impl Recurrence {
+    /// Returns `due` advanced by one recurrence interval.
+    pub fn advance(&self, due: SystemTime) -> SystemTime {
+        let interval = match self {
+            Recurrence::Daily => Duration::from_secs(SECS_PER_DAY),
+            Recurrence::Weekly => Duration::from_secs(7 * SECS_PER_DAY),
+            Recurrence::Every(duration) => *duration,
+        };
+        due + interval
+    }
+}
+
+/// An error parsing a natural-language or absolute due date.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DateParseError(pub String);
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse due date: {}", self.0)
+    }
+}
+
+impl std::error::Error for DateParseError {}
+
+/// Weekday index, Sunday = 0, matching the scheme used by `parse_weekday`.
+fn weekday_of(days_since_epoch: i64) -> i64 {
+    // 1970-01-01 (epoch day 0) was a Thursday.
+    (days_since_epoch + 4).rem_euclid(7)
+}
+
+fn parse_weekday(name: &str) -> Option<i64> {
+    let index = match name.to_ascii_lowercase().as_str() {
+        "sunday" => 0,
+        "monday" => 1,
+        "tuesday" => 2,
+        "wednesday" => 3,
+        "thursday" => 4,
+        "friday" => 5,
+        "saturday" => 6,
+        _ => return None,
+    };
+    Some(index)
+}
+
+/// Years further from the epoch than this are rejected rather than handed to
+/// `days_from_civil`, whose internal multiplications aren't overflow-checked.
+const MAX_YEAR_MAGNITUDE: i64 = 500_000;
+
+/// Converts a day offset from the Unix epoch into a `SystemTime`, rejecting (rather than
+/// panicking on) offsets that don't fit or would predate the epoch.
+fn day_to_systemtime(day: i64, phrase: &str) -> Result<SystemTime, DateParseError> {
+    if day < 0 {
+        return Err(DateParseError(phrase.to_string()));
+    }
+    let secs = (day as u64)
+        .checked_mul(SECS_PER_DAY)
+        .ok_or_else(|| DateParseError(phrase.to_string()))?;
+    UNIX_EPOCH
+        .checked_add(Duration::from_secs(secs))
+        .ok_or_else(|| DateParseError(phrase.to_string()))
+}
+
+/// Parses a human phrase ("tomorrow", "next friday", "in 3 days") or an absolute
+/// `YYYY-MM-DD` date, relative to `now`.
+fn parse_natural_date(phrase: &str, now: SystemTime) -> Result<SystemTime, DateParseError> {
+    let phrase = phrase.trim().to_ascii_lowercase();
+    let now_secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let today = (now_secs / SECS_PER_DAY) as i64;
+
+    if phrase == "today" {
+        return day_to_systemtime(today, &phrase);
+    }
+    if phrase == "tomorrow" {
+        return day_to_systemtime(today + 1, &phrase);
+    }
+
+    if let Some(rest) = phrase.strip_prefix("in ") {
+        let mut parts = rest.split_whitespace();
+        let count: i64 = parts
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| DateParseError(phrase.clone()))?;
+        let unit = parts.next().unwrap_or("");
+        if unit.starts_with("day") {
+            let target = today
+                .checked_add(count)
+                .ok_or_else(|| DateParseError(phrase.clone()))?;
+            return day_to_systemtime(target, &phrase);
+        }
+        if unit.starts_with("week") {
+            let offset = count
+                .checked_mul(7)
+                .ok_or_else(|| DateParseError(phrase.clone()))?;
+            let target = today
+                .checked_add(offset)
+                .ok_or_else(|| DateParseError(phrase.clone()))?;
+            return day_to_systemtime(target, &phrase);
+        }
+        return Err(DateParseError(phrase));
+    }
+
+    if let Some(day_name) = phrase.strip_prefix("next ") {
+        let target = parse_weekday(day_name).ok_or_else(|| DateParseError(phrase.clone()))?;
+        let current = weekday_of(today);
+        let mut delta = target - current;
+        if delta <= 0 {
+            delta += 7;
+        }
+        let target_day = today
+            .checked_add(delta)
+            .ok_or_else(|| DateParseError(phrase.clone()))?;
+        return day_to_systemtime(target_day, &phrase);
+    }
+
+    // Absolute date: YYYY-MM-DD.
+    let fields: Vec<&str> = phrase.split('-').collect();
+    if let [y, m, d] = &fields[..] {
+        let (year, month, day): (i64, u32, u32) = (
+            y.parse().map_err(|_| DateParseError(phrase.clone()))?,
+            m.parse().map_err(|_| DateParseError(phrase.clone()))?,
+            d.parse().map_err(|_| DateParseError(phrase.clone()))?,
+        );
+        if year.abs() > MAX_YEAR_MAGNITUDE {
+            return Err(DateParseError(phrase));
+        }
+        let days = days_from_civil(year, month, day);
+        return day_to_systemtime(days, &phrase);
+    }
+
+    Err(DateParseError(phrase))
+}
+
+impl Task {
+    /// Sets `due` by parsing a human phrase like "tomorrow", "next friday", "in 3 days", or
+    /// an absolute `YYYY-MM-DD` date.
+    pub fn set_due_from_str(&mut self, phrase: &str) -> Result<(), DateParseError> {
+        self.due = Some(parse_natural_date(phrase, SystemTime::now())?);
+        Ok(())
+    }
+}
+
+impl TaskCollection {
+    /// Pending tasks whose due date has passed.
+    pub fn overdue(&self) -> Vec<&Task> {
+        let now = SystemTime::now();
+        self.tasks
+            .iter()
+            .filter(|t| t.completed_at.is_none())
+            .filter(|t| t.due.is_some_and(|due| due < now))
+            .collect()
+    }
+
+    /// Pending tasks due today.
+    pub fn due_today(&self) -> Vec<&Task> {
+        let now = SystemTime::now();
+        let now_secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let today = now_secs / SECS_PER_DAY;
+        self.tasks
+            .iter()
+            .filter(|t| t.completed_at.is_none())
+            .filter(|t| {
+                t.due.is_some_and(|due| {
+                    let due_secs = due.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+                    due_secs / SECS_PER_DAY == today
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taskwarrior::civil_from_days;
+    use crate::Collection;
+
+    #[test]
+    fn parses_tomorrow() {
+        let now = SystemTime::now();
+        let tomorrow = parse_natural_date("tomorrow", now).unwrap();
+        assert!(tomorrow > now);
+        assert!(tomorrow < now + Duration::from_secs(2 * SECS_PER_DAY));
+    }
+
+    #[test]
+    fn parses_in_n_days() {
+        let now = SystemTime::now();
+        let due = parse_natural_date("in 3 days", now).unwrap();
+        assert!(due > now + Duration::from_secs(2 * SECS_PER_DAY));
+        assert!(due < now + Duration::from_secs(4 * SECS_PER_DAY));
+    }
+
+    #[test]
+    fn parses_absolute_date() {
+        let due = parse_natural_date("2023-01-01", UNIX_EPOCH).unwrap();
+        let days = due.duration_since(UNIX_EPOCH).unwrap().as_secs() / SECS_PER_DAY;
+        let (y, m, d) = civil_from_days(days as i64);
+        assert_eq!((y, m, d), (2023, 1, 1));
+    }
+
+    #[test]
+    fn rejects_unparseable_phrase() {
+        assert!(parse_natural_date("whenever", SystemTime::now()).is_err());
+    }
+
+    #[test]
+    fn rejects_an_overflowing_in_n_days_count_instead_of_panicking() {
+        let now = SystemTime::now();
+        assert!(parse_natural_date("in 9223372036854775807 days", now).is_err());
+        assert!(parse_natural_date("in 9223372036854775807 weeks", now).is_err());
+    }
+
+    #[test]
+    fn rejects_an_absurd_absolute_year_instead_of_panicking() {
+        let phrase = format!("{}-01-01", i64::MAX);
+        assert!(parse_natural_date(&phrase, SystemTime::now()).is_err());
+    }
+
+    #[test]
+    fn completing_a_recurring_task_spawns_the_next_instance() {
+        let mut task = Task::new("water the plants");
+        task.due = Some(SystemTime::now());
+        task.recurrence = Some(Recurrence::Daily);
+
+        let next = task.complete().expect("recurring task should spawn a successor");
+        assert_ne!(next.uuid, task.uuid);
+        assert_eq!(next.completed_at, None);
+        assert!(next.due.unwrap() > task.due.unwrap());
+    }
+
+    #[test]
+    fn overdue_and_due_today_filters() {
+        let mut collection = TaskCollection::new();
+        let mut overdue_task = Task::new("overdue");
+        overdue_task.due = Some(SystemTime::now() - Duration::from_secs(SECS_PER_DAY));
+        let mut today_task = Task::new("today");
+        // The last second of the current UTC day, which is the day `due_today` compares against.
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let end_of_today = (now_secs / SECS_PER_DAY + 1) * SECS_PER_DAY - 1;
+        today_task.due = Some(UNIX_EPOCH + Duration::from_secs(end_of_today));
+        collection.add_task(overdue_task);
+        collection.add_task(today_task);
+
+        assert_eq!(collection.overdue().len(), 1);
+        assert_eq!(collection.due_today().len(), 1);
+    }
+}
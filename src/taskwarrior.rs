@@ -0,0 +1,401 @@
+//! Interop with [Taskwarrior](https://taskwarrior.org)'s `task export`/`task import` JSON format.
+//!
+//! Taskwarrior's wire format is a flat JSON object per task with its own field names and its
+//! own timestamp format (`YYYYMMDDTHHMMSSZ`), so we translate explicitly instead of deriving
+//! `Serialize`/`Deserialize` directly on [`Task`].
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{Collection, Task, TaskCollection};
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TaskStatus {
+    Pending,
+    Completed,
+    Deleted,
+    Waiting,
+    Recurring,
+}
+
+impl TaskStatus {
+    fn as_taskwarrior_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Deleted => "deleted",
+            TaskStatus::Waiting => "waiting",
+            TaskStatus::Recurring => "recurring",
+        }
+    }
+
+    fn from_taskwarrior_str(s: &str) -> Result<Self, TaskwarriorError> {
+        match s {
+            "pending" => Ok(TaskStatus::Pending),
+            "completed" => Ok(TaskStatus::Completed),
+            "deleted" => Ok(TaskStatus::Deleted),
+            "waiting" => Ok(TaskStatus::Waiting),
+            "recurring" => Ok(TaskStatus::Recurring),
+            other => Err(TaskwarriorError::InvalidField(format!(
+                "unknown status `{other}`"
+            ))),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Priority {
+    H,
+    M,
+    L,
+}
+
+impl Priority {
+    fn as_taskwarrior_str(&self) -> &'static str {
+        match self {
+            Priority::H => "H",
+            Priority::M => "M",
+            Priority::L => "L",
+        }
+    }
+
+    fn from_taskwarrior_str(s: &str) -> Result<Self, TaskwarriorError> {
+        match s {
+            "H" => Ok(Priority::H),
+            "M" => Ok(Priority::M),
+            "L" => Ok(Priority::L),
+            other => Err(TaskwarriorError::InvalidField(format!(
+                "unknown priority `{other}`"
+            ))),
+        }
+    }
+}
+
+/// Errors that can occur while translating to or from Taskwarrior's JSON format.
+#[derive(Debug)]
+pub enum TaskwarriorError {
+    Json(serde_json::Error),
+    MissingField(&'static str),
+    InvalidField(String),
+}
+
+impl fmt::Display for TaskwarriorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskwarriorError::Json(e) => write!(f, "invalid taskwarrior json: {e}"),
+            TaskwarriorError::MissingField(field) => {
+                write!(f, "missing required field `{field}`")
+            }
+            TaskwarriorError::InvalidField(msg) => write!(f, "invalid field: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for TaskwarriorError {}
+
+impl From<serde_json::Error> for TaskwarriorError {
+    fn from(e: serde_json::Error) -> Self {
+        TaskwarriorError::Json(e)
+    }
+}
+
+const TW_DATE_FORMAT_LEN: usize = 16; // YYYYMMDDTHHMMSSZ
+
+/// Days since the Unix epoch -> (year, month, day), using Howard Hinnant's
+/// `civil_from_days` algorithm. Avoids pulling in a full datetime crate just for
+/// Taskwarrior's compact timestamp format.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+/// Inverse of [`civil_from_days`].
+pub(crate) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u64;
+    let mp = if m > 2 { m - 3 } else { m + 9 } as u64;
+    let doy = (153 * mp + 2) / 5 + d as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe as i64 - 719468
+}
+
+pub(crate) fn format_taskwarrior_date(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let (days, rem) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    let (hour, min, sec) = (rem / 3600, (rem / 60) % 60, rem % 60);
+    format!("{year:04}{month:02}{day:02}T{hour:02}{min:02}{sec:02}Z")
+}
+
+pub(crate) fn parse_taskwarrior_date(s: &str) -> Result<SystemTime, TaskwarriorError> {
+    // Byte length plus ASCII-only are both required before slicing by fixed byte offsets
+    // below; a non-ASCII char could make `s.len()` match while leaving the offsets landing
+    // mid-character, which would panic rather than fail gracefully.
+    if s.len() != TW_DATE_FORMAT_LEN || !s.is_ascii() || !s.ends_with('Z') {
+        return Err(TaskwarriorError::InvalidField(format!(
+            "bad taskwarrior timestamp `{s}`"
+        )));
+    }
+    let field = |range: std::ops::Range<usize>| -> Result<i64, TaskwarriorError> {
+        s.get(range)
+            .ok_or_else(|| TaskwarriorError::InvalidField(format!("bad taskwarrior timestamp `{s}`")))?
+            .parse()
+            .map_err(|_| TaskwarriorError::InvalidField(format!("bad taskwarrior timestamp `{s}`")))
+    };
+    let (year, month, day) = (field(0..4)?, field(4..6)? as u32, field(6..8)? as u32);
+    let (hour, min, sec) = (field(9..11)?, field(11..13)?, field(13..15)?);
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86400 + hour * 3600 + min * 60 + sec;
+    Ok(UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64))
+}
+
+impl Task {
+    /// Parses a single Taskwarrior task object, as produced by `task export`.
+    pub fn from_taskwarrior_json(json: &str) -> Result<Self, TaskwarriorError> {
+        let value: Value = serde_json::from_str(json)?;
+        Self::from_taskwarrior_value(&value)
+    }
+
+    fn from_taskwarrior_value(value: &Value) -> Result<Self, TaskwarriorError> {
+        let uuid_str = value
+            .get("uuid")
+            .and_then(Value::as_str)
+            .ok_or(TaskwarriorError::MissingField("uuid"))?;
+        let uuid = Uuid::parse_str(uuid_str)
+            .map_err(|e| TaskwarriorError::InvalidField(format!("uuid: {e}")))?;
+
+        let title = value
+            .get("description")
+            .and_then(Value::as_str)
+            .ok_or(TaskwarriorError::MissingField("description"))?
+            .to_string();
+
+        let entry = value
+            .get("entry")
+            .and_then(Value::as_str)
+            .map(parse_taskwarrior_date)
+            .transpose()?
+            .unwrap_or(UNIX_EPOCH);
+
+        let status = value
+            .get("status")
+            .and_then(Value::as_str)
+            .map(TaskStatus::from_taskwarrior_str)
+            .transpose()?
+            .unwrap_or(TaskStatus::Pending);
+
+        let priority = value
+            .get("priority")
+            .and_then(Value::as_str)
+            .map(Priority::from_taskwarrior_str)
+            .transpose()?;
+
+        let project = value
+            .get("project")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let tags = value
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let due = value
+            .get("due")
+            .and_then(Value::as_str)
+            .map(parse_taskwarrior_date)
+            .transpose()?;
+
+        let completed_at = value
+            .get("end")
+            .and_then(Value::as_str)
+            .map(parse_taskwarrior_date)
+            .transpose()?
+            .or(if status == TaskStatus::Completed {
+                Some(entry)
+            } else {
+                None
+            });
+
+        let depends_on = value
+            .get("depends")
+            .and_then(Value::as_str)
+            .map(|depends| {
+                depends
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| {
+                        Uuid::parse_str(s)
+                            .map_err(|e| TaskwarriorError::InvalidField(format!("depends: {e}")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Task {
+            uuid,
+            title,
+            description: "".to_string(),
+            entry,
+            status,
+            priority,
+            project,
+            tags,
+            due,
+            completed_at,
+            depends_on,
+            parent: None,
+            recurrence: None,
+        })
+    }
+
+    /// Serializes this task into a single Taskwarrior task object.
+    pub fn to_taskwarrior_json(&self) -> Result<String, TaskwarriorError> {
+        Ok(serde_json::to_string(&self.to_taskwarrior_value())?)
+    }
+
+    fn to_taskwarrior_value(&self) -> Value {
+        let mut obj = serde_json::Map::new();
+        obj.insert("uuid".into(), Value::String(self.uuid.to_string()));
+        obj.insert("description".into(), Value::String(self.title.clone()));
+        obj.insert(
+            "entry".into(),
+            Value::String(format_taskwarrior_date(self.entry)),
+        );
+        obj.insert(
+            "status".into(),
+            Value::String(self.status.as_taskwarrior_str().to_string()),
+        );
+        if let Some(priority) = self.priority {
+            obj.insert(
+                "priority".into(),
+                Value::String(priority.as_taskwarrior_str().to_string()),
+            );
+        }
+        if let Some(project) = &self.project {
+            obj.insert("project".into(), Value::String(project.clone()));
+        }
+        if !self.tags.is_empty() {
+            obj.insert(
+                "tags".into(),
+                Value::Array(self.tags.iter().cloned().map(Value::String).collect()),
+            );
+        }
+        if let Some(due) = self.due {
+            obj.insert("due".into(), Value::String(format_taskwarrior_date(due)));
+        }
+        if let Some(end) = self.completed_at {
+            obj.insert("end".into(), Value::String(format_taskwarrior_date(end)));
+        }
+        if !self.depends_on.is_empty() {
+            let depends = self
+                .depends_on
+                .iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            obj.insert("depends".into(), Value::String(depends));
+        }
+        Value::Object(obj)
+    }
+}
+
+impl TaskCollection {
+    /// Builds a new collection from the JSON array emitted by `task export`.
+    pub fn import_taskwarrior(json: &str) -> Result<Self, TaskwarriorError> {
+        let values: Vec<Value> = serde_json::from_str(json)?;
+        let tasks = values
+            .iter()
+            .map(Task::from_taskwarrior_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut collection = TaskCollection::new();
+        collection.tasks = tasks;
+        Ok(collection)
+    }
+
+    /// Exports this collection as a JSON array compatible with `task import`.
+    pub fn export_taskwarrior(&self) -> Result<String, TaskwarriorError> {
+        let values: Vec<Value> = self.tasks.iter().map(Task::to_taskwarrior_value).collect();
+        Ok(serde_json::to_string(&values)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_ascii_timestamp_without_panicking() {
+        // 16 bytes (matching TW_DATE_FORMAT_LEN) but only 15 chars, since 'é' is 2 bytes;
+        // naive byte-offset slicing would land mid-character here and panic.
+        let bad = "\u{e9}0230101T00000Z";
+        assert_eq!(bad.len(), 16);
+        assert!(parse_taskwarrior_date(bad).is_err());
+    }
+
+    #[test]
+    fn round_trips_a_minimal_taskwarrior_task() {
+        let json = r#"{
+            "uuid": "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8",
+            "description": "buy milk",
+            "entry": "20230101T000000Z",
+            "status": "pending"
+        }"#;
+        let task = Task::from_taskwarrior_json(json).unwrap();
+        assert_eq!(task.title, "buy milk");
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert_eq!(task.completed_at, None);
+
+        let exported = task.to_taskwarrior_json().unwrap();
+        let roundtripped = Task::from_taskwarrior_json(&exported).unwrap();
+        assert_eq!(roundtripped, task);
+    }
+
+    #[test]
+    fn completed_status_maps_to_completed_at() {
+        let json = r#"{
+            "uuid": "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8",
+            "description": "buy milk",
+            "entry": "20230101T000000Z",
+            "end": "20230102T000000Z",
+            "status": "completed"
+        }"#;
+        let task = Task::from_taskwarrior_json(json).unwrap();
+        assert!(task.completed_at.is_some());
+    }
+
+    #[test]
+    fn import_export_collection() {
+        let mut collection = TaskCollection::new();
+        collection.add_task(Task::new("one"));
+        collection.add_task(Task::new("two"));
+
+        let json = collection.export_taskwarrior().unwrap();
+        let reimported = TaskCollection::import_taskwarrior(&json).unwrap();
+        assert_eq!(reimported.tasks.len(), 2);
+    }
+}
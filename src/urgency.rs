@@ -0,0 +1,160 @@
+//! Urgency scoring, mirroring Taskwarrior's `urgency` coefficients.
+
+use std::time::SystemTime;
+
+use crate::{Priority, Task, TaskCollection, TaskStatus};
+
+/// Coefficients used by [`Task::urgency`]. The defaults match Taskwarrior's out-of-the-box
+/// weights closely enough to feel familiar, but every term can be re-weighted.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UrgencyConfig {
+    pub priority_high: f64,
+    pub priority_medium: f64,
+    pub priority_low: f64,
+    pub due_coefficient: f64,
+    pub tags_coefficient: f64,
+    pub project_coefficient: f64,
+    pub age_coefficient: f64,
+    /// Age, in seconds, at which the age term saturates at its maximum contribution.
+    pub max_age_secs: f64,
+}
+
+impl Default for UrgencyConfig {
+    fn default() -> Self {
+        Self {
+            priority_high: 6.0,
+            priority_medium: 3.9,
+            priority_low: 1.8,
+            due_coefficient: 12.0,
+            tags_coefficient: 1.0,
+            project_coefficient: 1.0,
+            age_coefficient: 2.0,
+            max_age_secs: 365.0 * 24.0 * 3600.0,
+        }
+    }
+}
+
+impl Task {
+    /// Computes this task's urgency under the default [`UrgencyConfig`]. Tasks whose status
+    /// is `Completed` or `Deleted` always score zero since there's nothing left to prioritize.
+    pub fn urgency(&self) -> f64 {
+        self.urgency_with(&UrgencyConfig::default())
+    }
+
+    /// Computes this task's urgency as a weighted sum of priority, due date, tags, project,
+    /// and age terms, under the given [`UrgencyConfig`].
+    pub fn urgency_with(&self, config: &UrgencyConfig) -> f64 {
+        if self.status == TaskStatus::Completed || self.status == TaskStatus::Deleted {
+            return 0.0;
+        }
+
+        let priority_term = match self.priority {
+            Some(Priority::H) => config.priority_high,
+            Some(Priority::M) => config.priority_medium,
+            Some(Priority::L) => config.priority_low,
+            None => 0.0,
+        };
+
+        let due_term = self.due.map_or(0.0, |due| {
+            let now = SystemTime::now();
+            let urgency_fraction = match due.duration_since(now) {
+                // Due date has passed (or is now): fully urgent.
+                Err(_) => 1.0,
+                Ok(remaining) => {
+                    let days_remaining = remaining.as_secs_f64() / (24.0 * 3600.0);
+                    // Ramps from 0.2 (far off) up to 1.0 as the due date approaches,
+                    // saturating at 14 days out, same shape as Taskwarrior's default.
+                    let fraction = 1.0 - (days_remaining / 14.0).clamp(0.0, 1.0) * 0.8;
+                    fraction.clamp(0.2, 1.0)
+                }
+            };
+            urgency_fraction * config.due_coefficient
+        });
+
+        let tags_term = if self.tags.is_empty() {
+            0.0
+        } else {
+            config.tags_coefficient
+        };
+
+        let project_term = if self.project.is_some() {
+            config.project_coefficient
+        } else {
+            0.0
+        };
+
+        let age_term = SystemTime::now()
+            .duration_since(self.entry)
+            .map(|age| (age.as_secs_f64() / config.max_age_secs.max(1.0)).min(1.0))
+            .unwrap_or(0.0)
+            * config.age_coefficient;
+
+        priority_term + due_term + tags_term + project_term + age_term
+    }
+}
+
+impl TaskCollection {
+    /// Returns the tasks in this collection ordered most-urgent-first.
+    pub fn sorted_by_urgency(&self) -> Vec<&Task> {
+        self.sorted_by_urgency_with(&UrgencyConfig::default())
+    }
+
+    /// Like [`TaskCollection::sorted_by_urgency`], but scored under a custom [`UrgencyConfig`].
+    pub fn sorted_by_urgency_with(&self, config: &UrgencyConfig) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.iter().collect();
+        tasks.sort_by(|a, b| {
+            b.urgency_with(config)
+                .partial_cmp(&a.urgency_with(config))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        tasks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collection;
+
+    #[test]
+    fn completed_tasks_have_zero_urgency() {
+        let mut task = Task::new("done");
+        task.complete();
+        assert_eq!(task.urgency(), 0.0);
+    }
+
+    #[test]
+    fn higher_priority_is_more_urgent() {
+        let mut low = Task::new("low priority");
+        low.priority = Some(Priority::L);
+        let mut high = Task::new("high priority");
+        high.priority = Some(Priority::H);
+        assert!(high.urgency() > low.urgency());
+    }
+
+    #[test]
+    fn an_overdue_task_outranks_a_merely_medium_priority_one() {
+        let mut overdue = Task::new("overdue, no priority");
+        overdue.due = Some(SystemTime::now() - std::time::Duration::from_secs(3600));
+
+        let mut medium_priority = Task::new("medium priority, no due date");
+        medium_priority.priority = Some(Priority::M);
+
+        assert!(overdue.urgency() > medium_priority.urgency());
+    }
+
+    #[test]
+    fn sorts_most_urgent_first() {
+        let mut collection = TaskCollection::new();
+        let mut low = Task::new("low");
+        low.priority = Some(Priority::L);
+        let mut high = Task::new("high");
+        high.priority = Some(Priority::H);
+        collection.add_task(low.clone());
+        collection.add_task(high.clone());
+
+        let sorted = collection.sorted_by_urgency();
+        assert_eq!(sorted[0].title, high.title);
+    }
+}